@@ -28,61 +28,341 @@
  *                                                                            *
 \* -------------------------------------------------------------------------- */
 
-use super::{Cell, CellName, CellsError, Result};
-use std::{collections::HashMap, sync::Arc};
+use super::{Cell, CellName, CellsError, Resource, Result};
+use async_trait::async_trait;
+use std::{
+    any::Any,
+    collections::HashMap,
+    ffi::OsStr,
+    fmt::Debug,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use tokio::sync::{Mutex, MutexGuard};
 
 type Cache = HashMap<CellName, Cell>;
+type BoxedAny = Box<dyn Any + Send>;
 
-/// Cells is the in-memory store for the list of cells created with Aurae.
-#[derive(Debug, Default, Clone)]
-pub(crate) struct Cells {
-    cache: Arc<Mutex<Cache>>,
-}
+/// The storage surface behind [MemoryCellStore]: allocate, look up, mutate,
+/// and free cells by name.
+///
+/// `allocate`/`get_erased`/`get_mut_erased`/`allocate_then_erased`/`free`
+/// are the object-safe primitives, so the daemon can hold a `Box<dyn CellStore>` and swap in a
+/// persistent store or a test double without touching callers. The
+/// closure-taking `get`/`get_mut`/`allocate_then` are provided as default,
+/// generic methods on top of those primitives (`where Self: Sized`, so they
+/// don't block the trait from being object safe) purely so existing
+/// call sites keep compiling unchanged.
+#[async_trait]
+pub(crate) trait CellStore: Send + Sync + Debug {
+    /// Add the [Cell] to the store with key [CellName].
+    /// Returns an error if a duplicate [CellName] already exists.
+    async fn allocate(&self, cell_name: CellName, cell: Cell) -> Result<()>;
 
-// TODO: add to the impl
-// - Get Cgroup from cell_name
-// - Get Cgroup from executable_name
-// - Get Cgroup from pid
-// - Get Cgroup and pids from executable_name
+    /// Runs `f` against the stored cell and returns its type-erased result.
+    /// Prefer [CellStore::get] unless you're implementing the trait itself.
+    async fn get_erased(
+        &self,
+        cell_name: &CellName,
+        f: Box<dyn FnOnce(&Cell) -> Result<BoxedAny> + Send>,
+    ) -> Result<BoxedAny>;
+
+    /// Runs `f` against the stored cell and returns its type-erased result.
+    /// Prefer [CellStore::get_mut] unless you're implementing the trait itself.
+    async fn get_mut_erased(
+        &self,
+        cell_name: &CellName,
+        f: Box<dyn FnOnce(&mut Cell) -> Result<BoxedAny> + Send>,
+    ) -> Result<BoxedAny>;
+
+    /// Returns an error if the [CellName] does not exist in the store.
+    async fn free(&self, cell_name: &CellName) -> Result<()>;
 
-impl Cells {
-    /// Add the [Cell] to the cache with key [CellName].
-    /// Returns an error if a duplicate [CellName] already exists in the cache.
-    pub async fn allocate<T: Into<Cell>>(
+    /// Inserts the [Cell] and runs `f` against it as a single critical
+    /// section, then returns its type-erased result. Prefer
+    /// [CellStore::allocate_then] unless you're implementing the trait
+    /// itself — this exists so the insert and `f` never release the lock
+    /// in between, which would let a concurrent `free` or capacity-driven
+    /// eviction remove the cell before `f` runs on it.
+    async fn allocate_then_erased(
         &self,
         cell_name: CellName,
-        cell: T,
-    ) -> Result<()> {
-        let mut cache = self.cache.lock().await;
-        let _ = allocate(&mut cache, cell_name, cell)?;
-        Ok(())
-    }
+        cell: Cell,
+        f: Box<dyn FnOnce(&Cell) -> Result<BoxedAny> + Send>,
+    ) -> Result<BoxedAny>;
 
-    /// See [allocate]
-    pub async fn allocate_then<T: Into<Cell>, F, R>(
+    /// See [CellStore::allocate].
+    async fn allocate_then<T, F, R>(
         &self,
         cell_name: CellName,
         cell: T,
         f: F,
     ) -> Result<R>
     where
-        F: Fn(&Cell) -> Result<R>,
+        Self: Sized,
+        T: Into<Cell> + Send,
+        F: Fn(&Cell) -> Result<R> + Send + 'static,
+        R: Send + 'static,
     {
-        let mut cache = self.cache.lock().await;
-        let cell = allocate(&mut cache, cell_name, cell)?;
-        f(cell)
+        let boxed = self
+            .allocate_then_erased(
+                cell_name,
+                cell.into(),
+                Box::new(move |cell| f(cell).map(|r| Box::new(r) as BoxedAny)),
+            )
+            .await?;
+        Ok(*boxed.downcast::<R>().expect("R is the type f was called with"))
     }
 
-    pub async fn get<F, R>(&self, cell_name: &CellName, f: F) -> Result<R>
+    async fn get<F, R>(&self, cell_name: &CellName, f: F) -> Result<R>
     where
-        F: Fn(&Cell) -> Result<R>,
+        Self: Sized,
+        F: Fn(&Cell) -> Result<R> + Send + 'static,
+        R: Send + 'static,
     {
-        let mut cache = self.cache.lock().await;
-        if let Some(cell) = cache.get(cell_name) {
+        let boxed = self
+            .get_erased(
+                cell_name,
+                Box::new(move |cell| f(cell).map(|r| Box::new(r) as BoxedAny)),
+            )
+            .await?;
+        Ok(*boxed.downcast::<R>().expect("R is the type f was called with"))
+    }
+
+    async fn get_mut<F, R>(&self, cell_name: &CellName, f: F) -> Result<R>
+    where
+        Self: Sized,
+        F: FnOnce(&mut Cell) -> Result<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let boxed = self
+            .get_mut_erased(
+                cell_name,
+                Box::new(move |cell| f(cell).map(|r| Box::new(r) as BoxedAny)),
+            )
+            .await?;
+        Ok(*boxed.downcast::<R>().expect("R is the type f was called with"))
+    }
+}
+
+/// The in-memory [CellStore]: cells live only as long as the daemon process.
+/// It is currently the only implementation of the trait.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct MemoryCellStore {
+    inner: Arc<Mutex<Inner>>,
+}
+
+/// Kept as an alias so existing call sites that name the type directly
+/// (struct/field declarations, `Cells::default()`, `Cells::with_capacity`)
+/// keep compiling unchanged after the `CellStore` trait was introduced.
+pub(crate) type Cells = MemoryCellStore;
+
+/// Everything that lives behind the single lock: the cell table itself,
+/// the access-ordered recency list used for LRU reclamation, the (optional)
+/// capacity that bounds the table, and the (optional) host-wide resource
+/// budget enforced at allocation time.
+#[derive(Debug, Default)]
+struct Inner {
+    cells: Cache,
+    recency: Recency,
+    capacity: Option<usize>,
+    budget: Option<Budget>,
+}
+
+/// The configured limits of a single [Cell], as counted by the accounting
+/// subsystem. A `None` field means the cell did not configure a limit for
+/// that resource, so it does not count against a host-wide [Budget].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct CellLimits {
+    pub memory_bytes: Option<i64>,
+    pub cpu_quota_micros: Option<i64>,
+    pub pids: Option<i64>,
+}
+
+/// An optional host-wide cap per resource. `None` leaves that resource
+/// unconstrained.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Budget {
+    pub memory_bytes: Option<i64>,
+    pub cpu_quota_micros: Option<i64>,
+    pub pids: Option<i64>,
+}
+
+/// The sum of [CellLimits] across every cell currently in the cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct ResourceTotals {
+    pub memory_bytes: i64,
+    pub cpu_quota_micros: i64,
+    pub pids: i64,
+}
+
+/// A snapshot of aggregate resource usage across all cells, returned by
+/// [MemoryCellStore::usage].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Usage {
+    pub totals: ResourceTotals,
+    pub per_cell: Vec<(CellName, CellLimits)>,
+}
+
+// TODO: add to the impl
+// - Get Cgroup from cell_name
+// - Get Cgroup from executable_name
+// - Get Cgroup from pid
+// - Get Cgroup and pids from executable_name
+
+impl MemoryCellStore {
+    fn new(capacity: Option<usize>, budget: Option<Budget>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                cells: Cache::default(),
+                recency: Recency::default(),
+                capacity,
+                budget,
+            })),
+        }
+    }
+
+    /// Creates a [MemoryCellStore] that reclaims idle (no running
+    /// executables) cells, least-recently-used first, once `capacity` cells
+    /// are allocated.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::new(Some(capacity), None)
+    }
+
+    /// Creates a [MemoryCellStore] that rejects an `allocate` which would
+    /// push the aggregate configured limits of its cells past `budget`.
+    pub fn with_budget(budget: Budget) -> Self {
+        Self::new(None, Some(budget))
+    }
+
+    /// Creates a [MemoryCellStore] that enforces both a cell-count capacity
+    /// and a resource budget at once — the two are independent protections
+    /// and are meant to be composable.
+    pub fn with_capacity_and_budget(capacity: usize, budget: Budget) -> Self {
+        Self::new(Some(capacity), Some(budget))
+    }
+
+    /// A snapshot of the configured limits summed across every cell
+    /// currently in the cache, alongside each cell's own breakdown.
+    pub async fn usage(&self) -> Usage {
+        let inner = self.inner.lock().await;
+        Usage {
+            totals: aggregate(&inner.cells),
+            per_cell: inner
+                .cells
+                .iter()
+                .map(|(name, cell)| (name.clone(), cell.resource_limits()))
+                .collect(),
+        }
+    }
+
+    /// Walks `root` (Aurae's slice of the cgroup v2 hierarchy) and adopts
+    /// every subdirectory not already in the cache, so cells created by a
+    /// previous run of the daemon are rediscovered instead of leaked. A
+    /// fresh [MemoryCellStore] starts out empty, but the cgroups it
+    /// previously created survive a daemon restart on disk.
+    ///
+    /// `to_cell` reconstructs the [Cell] for a discovered cgroup — the store
+    /// only knows how to enumerate directories, not how to rebuild the
+    /// cell-specific state (limits, executables) that produced one. A
+    /// cgroup that exists on disk but can't be re-read is recorded in the
+    /// returned report's `failed` list rather than aborting the whole pass,
+    /// so the caller can decide whether to start with a partial
+    /// reconciliation or abort.
+    pub async fn reconcile<T: Into<Cell>>(
+        &self,
+        root: &Path,
+        to_cell: impl Fn(&CellName, &Path) -> Result<T>,
+    ) -> Result<ReconciliationReport> {
+        // `read_dir` and `DirEntry::file_type` are blocking syscalls; run
+        // the scan on a blocking-pool thread so it doesn't stall the tokio
+        // worker (and whatever else is waiting on `self.inner`) behind disk
+        // I/O.
+        let root = root.to_path_buf();
+        let discovered =
+            tokio::task::spawn_blocking(move || discover_cell_dirs(&root))
+                .await
+                .expect("cell reconciliation scan task panicked")?;
+
+        let mut report = ReconciliationReport::default();
+        let mut inner = self.inner.lock().await;
+        for (cell_name, path) in discovered {
+            if inner.cells.contains_key(&cell_name) {
+                continue;
+            }
+
+            match to_cell(&cell_name, &path) {
+                Ok(cell) => {
+                    let _ =
+                        inner.cells.insert(cell_name.clone(), cell.into());
+                    inner.recency.touch(&cell_name);
+                    report.adopted.push(cell_name);
+                }
+                Err(source) => {
+                    report.failed.push((cell_name, Box::new(source)));
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Lists the immediate subdirectories of `root`, paired with the [CellName]
+/// each represents. Pulled out of [MemoryCellStore::reconcile] so the
+/// blocking I/O it does can run via `spawn_blocking`.
+fn discover_cell_dirs(root: &Path) -> Result<Vec<(CellName, PathBuf)>> {
+    let entries = fs::read_dir(root).map_err(|source| {
+        CellsError::FailedToReadCgroupRoot { root: root.to_path_buf(), source }
+    })?;
+
+    let mut dirs = Vec::new();
+    for entry in entries.flatten() {
+        if !entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+            continue;
+        }
+
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(OsStr::to_str) else {
+            continue;
+        };
+        dirs.push((CellName::from(name), path));
+    }
+
+    Ok(dirs)
+}
+
+/// Outcome of a [MemoryCellStore::reconcile] pass: which cells were
+/// successfully adopted back into the cache, and which existed on disk but
+/// could not be re-read.
+#[derive(Debug, Default)]
+pub(crate) struct ReconciliationReport {
+    pub adopted: Vec<CellName>,
+    pub failed: Vec<(CellName, Box<CellsError>)>,
+}
+
+#[async_trait]
+impl CellStore for MemoryCellStore {
+    async fn allocate(&self, cell_name: CellName, cell: Cell) -> Result<()> {
+        let mut inner = self.inner.lock().await;
+        let _ = allocate(&mut inner, cell_name, cell)?;
+        Ok(())
+    }
+
+    async fn get_erased(
+        &self,
+        cell_name: &CellName,
+        f: Box<dyn FnOnce(&Cell) -> Result<BoxedAny> + Send>,
+    ) -> Result<BoxedAny> {
+        let mut inner = self.inner.lock().await;
+        if let Some(cell) = inner.cells.get(cell_name) {
             let res = f(cell);
-            if matches!(res, Err(CellsError::CellUnallocated { .. })) {
-                let _ = cache.remove(cell_name);
+            if matches!(res, Err(CellsError::CellNotAllocated { .. })) {
+                let _ = inner.cells.remove(cell_name);
+                inner.recency.remove(cell_name);
+            } else {
+                inner.recency.touch(cell_name);
             }
             res
         } else {
@@ -90,27 +370,40 @@ impl Cells {
         }
     }
 
-    pub async fn get_mut<F, R>(&self, cell_name: &CellName, f: F) -> Result<R>
-    where
-        F: FnOnce(&mut Cell) -> Result<R>,
-    {
-        let mut cache = self.cache.lock().await;
-        get_mut(&mut cache, cell_name, f)
+    async fn get_mut_erased(
+        &self,
+        cell_name: &CellName,
+        f: Box<dyn FnOnce(&mut Cell) -> Result<BoxedAny> + Send>,
+    ) -> Result<BoxedAny> {
+        let mut inner = self.inner.lock().await;
+        get_mut(&mut inner, cell_name, f)
     }
 
-    /// Returns an error if the [CellName] does not exist in the cache.
-    pub async fn free(&self, cell_name: &CellName) -> Result<()> {
-        let mut cache = self.cache.lock().await;
-        get_mut(&mut cache, cell_name, |cell| cell.free())?;
-        let _ = cache.remove(cell_name).ok_or_else(|| {
+    async fn allocate_then_erased(
+        &self,
+        cell_name: CellName,
+        cell: Cell,
+        f: Box<dyn FnOnce(&Cell) -> Result<BoxedAny> + Send>,
+    ) -> Result<BoxedAny> {
+        let mut inner = self.inner.lock().await;
+        let cell = allocate(&mut inner, cell_name, cell)?;
+        f(cell)
+    }
+
+    /// Returns an error if the [CellName] does not exist in the store.
+    async fn free(&self, cell_name: &CellName) -> Result<()> {
+        let mut inner = self.inner.lock().await;
+        get_mut(&mut inner, cell_name, |cell| cell.free())?;
+        let _ = inner.cells.remove(cell_name).ok_or_else(|| {
             CellsError::CellNotFound { cell_name: cell_name.clone() }
         })?;
+        inner.recency.remove(cell_name);
         Ok(())
     }
 }
 
 fn allocate<'a, T: Into<Cell>>(
-    cache: &'a mut MutexGuard<Cache>,
+    inner: &'a mut MutexGuard<Inner>,
     cell_name: CellName,
     cell: T,
 ) -> Result<&'a Cell> {
@@ -118,28 +411,179 @@ fn allocate<'a, T: Into<Cell>>(
     // cache.try_insert(cell_name.clone(), cgroup)
 
     // Check if there was already a cgroup in the table with this cell name as a key.
-    if cache.contains_key(&cell_name) {
+    if inner.cells.contains_key(&cell_name) {
         return Err(CellsError::CellExists { cell_name });
     }
 
+    let cell: Cell = cell.into();
+
+    // Work out, without evicting anything yet, whether capacity forces a
+    // reclaim and which cell would be reclaimed, so the budget check below
+    // can judge the allocation against the post-reclaim totals instead of
+    // counting a cell that's about to be freed as still using resources.
+    let reclaim_candidate = match inner.capacity {
+        Some(capacity) if inner.cells.len() >= capacity => Some(
+            find_reclaim_candidate(&inner.cells, &inner.recency, capacity, &cell_name)?,
+        ),
+        _ => None,
+    };
+
+    // Check the budget up front, before anything is evicted or inserted: a
+    // try-reserve discipline. Eviction (below) destroys an idle cell's
+    // cgroup, which can't be undone — so a rejected allocation must be
+    // caught before that happens, not after, or a cell gets freed for
+    // nothing.
+    if let Some(budget) = inner.budget {
+        check_budget(
+            &inner.cells,
+            reclaim_candidate.as_ref(),
+            budget,
+            &cell_name,
+            cell.resource_limits(),
+        )?;
+    }
+
+    if let Some(candidate) = &reclaim_candidate {
+        evict(inner, candidate)?;
+    }
+
     // `or_insert` will always insert as we've already assured ourselves that the key does not exist.
-    let cell = cache.entry(cell_name).or_insert_with(|| cell.into());
+    let cell = inner.cells.entry(cell_name.clone()).or_insert_with(|| cell);
     cell.allocate();
+    inner.recency.touch(&cell_name);
     Ok(cell)
 }
 
+/// Sums the configured limits of every cell currently in the cache.
+fn aggregate(cells: &Cache) -> ResourceTotals {
+    aggregate_excluding(cells, None)
+}
+
+/// Sums the configured limits of every cell in the cache except `exclude`,
+/// so a prospective reclaim victim can be left out of the totals before it's
+/// actually evicted.
+fn aggregate_excluding(
+    cells: &Cache,
+    exclude: Option<&CellName>,
+) -> ResourceTotals {
+    cells
+        .iter()
+        .filter(|(name, _)| exclude != Some(*name))
+        .map(|(_, cell)| cell.resource_limits())
+        .fold(ResourceTotals::default(), |mut totals, limits| {
+            totals.memory_bytes += limits.memory_bytes.unwrap_or(0);
+            totals.cpu_quota_micros += limits.cpu_quota_micros.unwrap_or(0);
+            totals.pids += limits.pids.unwrap_or(0);
+            totals
+        })
+}
+
+/// Rejects an allocation that would push the aggregate configured limits of
+/// the cache past `budget`, one resource at a time. `exclude` is the cell
+/// that capacity-driven reclamation is about to evict (if any) — it's left
+/// out of the totals since it won't be around by the time this cell lands.
+fn check_budget(
+    cells: &Cache,
+    exclude: Option<&CellName>,
+    budget: Budget,
+    cell_name: &CellName,
+    requested: CellLimits,
+) -> Result<()> {
+    let totals = aggregate_excluding(cells, exclude);
+    check_resource(
+        budget.memory_bytes,
+        totals.memory_bytes,
+        requested.memory_bytes,
+        cell_name,
+        Resource::Memory,
+    )?;
+    check_resource(
+        budget.cpu_quota_micros,
+        totals.cpu_quota_micros,
+        requested.cpu_quota_micros,
+        cell_name,
+        Resource::CpuQuota,
+    )?;
+    check_resource(
+        budget.pids,
+        totals.pids,
+        requested.pids,
+        cell_name,
+        Resource::Pids,
+    )
+}
+
+fn check_resource(
+    budget: Option<i64>,
+    current_total: i64,
+    requested: Option<i64>,
+    cell_name: &CellName,
+    resource: Resource,
+) -> Result<()> {
+    let (Some(available), Some(requested)) = (budget, requested) else {
+        // Either the host didn't cap this resource, or the cell didn't
+        // request any of it — nothing to enforce.
+        return Ok(());
+    };
+
+    if current_total + requested > available {
+        return Err(CellsError::BudgetExceeded {
+            cell_name: cell_name.clone(),
+            resource,
+            requested,
+            available: (available - current_total).max(0),
+        });
+    }
+
+    Ok(())
+}
+
+/// Finds the least-recently-used cell that currently holds no running
+/// executables, without evicting it — a cell with live executables is never
+/// a candidate. Split out from eviction itself so a caller can factor the
+/// candidate into other decisions (like a budget check) before committing
+/// to evict it.
+fn find_reclaim_candidate(
+    cells: &Cache,
+    recency: &Recency,
+    capacity: usize,
+    cell_name: &CellName,
+) -> Result<CellName> {
+    recency
+        .lru_to_mru()
+        .into_iter()
+        .find(|name| cells.get(name).is_some_and(Cell::is_empty))
+        .ok_or_else(|| CellsError::CapacityExceeded {
+            cell_name: cell_name.clone(),
+            capacity,
+        })
+}
+
+/// Frees `candidate`'s cgroup and drops it from the cache and recency list.
+fn evict(inner: &mut MutexGuard<Inner>, candidate: &CellName) -> Result<()> {
+    if let Some(cell) = inner.cells.get_mut(candidate) {
+        cell.free()?;
+    }
+    let _ = inner.cells.remove(candidate);
+    inner.recency.remove(candidate);
+    Ok(())
+}
+
 fn get_mut<F, R>(
-    cache: &mut MutexGuard<Cache>,
+    inner: &mut MutexGuard<Inner>,
     cell_name: &CellName,
     f: F,
 ) -> Result<R>
 where
     F: FnOnce(&mut Cell) -> Result<R>,
 {
-    if let Some(cell) = cache.get_mut(cell_name) {
+    if let Some(cell) = inner.cells.get_mut(cell_name) {
         let res = f(cell);
-        if matches!(res, Err(CellsError::CellUnallocated { .. })) {
-            let _ = cache.remove(cell_name);
+        if matches!(res, Err(CellsError::CellNotAllocated { .. })) {
+            let _ = inner.cells.remove(cell_name);
+            inner.recency.remove(cell_name);
+        } else {
+            inner.recency.touch(cell_name);
         }
         res
     } else {
@@ -147,8 +591,235 @@ where
     }
 }
 
+/// An intrusive, access-ordered doubly-linked list over [CellName]s, so a
+/// cell is touched or removed in O(1) without re-walking the cache;
+/// `lru_to_mru` walks the whole list from the tail.
+/// Nodes are keyed by [CellName] rather than a literal array index so that
+/// `touch`/`remove` don't need to shift anything around.
+#[derive(Debug, Default)]
+struct Recency {
+    nodes: HashMap<CellName, Node>,
+    head: Option<CellName>,
+    tail: Option<CellName>,
+}
+
+#[derive(Debug)]
+struct Node {
+    prev: Option<CellName>,
+    next: Option<CellName>,
+}
+
+impl Recency {
+    /// Marks `cell_name` as the most-recently-used entry, inserting it if
+    /// it isn't already tracked.
+    fn touch(&mut self, cell_name: &CellName) {
+        if let Some(node) = self.nodes.remove(cell_name) {
+            self.unlink(&node);
+        }
+        self.push_front(cell_name.clone());
+    }
+
+    /// Unlinks `cell_name` from the list. A no-op if it isn't tracked.
+    fn remove(&mut self, cell_name: &CellName) {
+        if let Some(node) = self.nodes.remove(cell_name) {
+            self.unlink(&node);
+        }
+    }
+
+    /// Returns cell names ordered from least- to most-recently-used.
+    fn lru_to_mru(&self) -> Vec<CellName> {
+        let mut order = Vec::with_capacity(self.nodes.len());
+        let mut current = self.tail.clone();
+        while let Some(cell_name) = current {
+            current = self.nodes.get(&cell_name).and_then(|n| n.prev.clone());
+            order.push(cell_name);
+        }
+        order
+    }
+
+    fn push_front(&mut self, cell_name: CellName) {
+        let old_head = self.head.replace(cell_name.clone());
+        if let Some(old_head) = &old_head {
+            if let Some(node) = self.nodes.get_mut(old_head) {
+                node.prev = Some(cell_name.clone());
+            }
+        } else {
+            self.tail = Some(cell_name.clone());
+        }
+        self.nodes.insert(cell_name, Node { prev: None, next: old_head });
+    }
+
+    fn unlink(&mut self, node: &Node) {
+        match (&node.prev, &node.next) {
+            (Some(prev), Some(next)) => {
+                if let Some(n) = self.nodes.get_mut(prev) {
+                    n.next = Some(next.clone());
+                }
+                if let Some(n) = self.nodes.get_mut(next) {
+                    n.prev = Some(prev.clone());
+                }
+            }
+            (Some(prev), None) => {
+                if let Some(n) = self.nodes.get_mut(prev) {
+                    n.next = None;
+                }
+                self.tail = Some(prev.clone());
+            }
+            (None, Some(next)) => {
+                if let Some(n) = self.nodes.get_mut(next) {
+                    n.prev = None;
+                }
+                self.head = Some(next.clone());
+            }
+            (None, None) => {
+                self.head = None;
+                self.tail = None;
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::{check_resource, CellsError, Recency, Resource};
+
+    // `check_resource` is the per-resource enforcement `check_budget` folds
+    // over; exercised directly because `check_budget`/`aggregate` take a
+    // `Cache` of real `Cell`s, and `Cell` is defined outside this module
+    // with no way to construct one here.
+
+    #[test]
+    fn check_resource_allows_requests_within_budget() {
+        let result =
+            check_resource(Some(100), 40, Some(50), &"a".into(), Resource::Pids);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn check_resource_allows_a_request_that_exactly_fills_the_budget() {
+        let result =
+            check_resource(Some(100), 40, Some(60), &"a".into(), Resource::Pids);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn check_resource_rejects_a_request_that_would_oversubscribe() {
+        let result = check_resource(
+            Some(100),
+            80,
+            Some(30),
+            &"a".into(),
+            Resource::Memory,
+        );
+
+        match result {
+            Err(CellsError::BudgetExceeded {
+                resource: Resource::Memory,
+                requested: 30,
+                available: 20,
+                ..
+            }) => {}
+            other => panic!("expected BudgetExceeded, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn check_resource_reports_zero_available_when_already_oversubscribed() {
+        let result = check_resource(
+            Some(100),
+            150,
+            Some(1),
+            &"a".into(),
+            Resource::CpuQuota,
+        );
+
+        match result {
+            Err(CellsError::BudgetExceeded { available: 0, .. }) => {}
+            other => panic!("expected BudgetExceeded, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn check_resource_is_unconstrained_when_the_host_set_no_budget() {
+        let result =
+            check_resource(None, 0, Some(i64::MAX), &"a".into(), Resource::Pids);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn check_resource_is_unconstrained_when_the_cell_requested_nothing() {
+        let result = check_resource(Some(0), 0, None, &"a".into(), Resource::Pids);
+
+        assert!(result.is_ok());
+    }
+
+    // `Recency` is exercised directly because `find_reclaim_candidate`'s
+    // eviction order and its "never evict a non-empty cell" invariant both
+    // reduce to whether this list is ordered correctly; `Cell` itself is
+    // defined outside this module and can't be constructed here to drive
+    // `find_reclaim_candidate` end-to-end.
+
+    #[test]
+    fn lru_to_mru_orders_oldest_touch_first() {
+        let mut recency = Recency::default();
+        recency.touch(&"a".into());
+        recency.touch(&"b".into());
+        recency.touch(&"c".into());
+
+        assert_eq!(
+            recency.lru_to_mru(),
+            vec!["a".into(), "b".into(), "c".into()]
+        );
+    }
+
+    #[test]
+    fn touch_moves_an_existing_entry_to_most_recently_used() {
+        let mut recency = Recency::default();
+        recency.touch(&"a".into());
+        recency.touch(&"b".into());
+        recency.touch(&"c".into());
+
+        recency.touch(&"a".into());
+
+        assert_eq!(
+            recency.lru_to_mru(),
+            vec!["b".into(), "c".into(), "a".into()]
+        );
+    }
+
+    #[test]
+    fn remove_unlinks_tail_head_and_sole_entries() {
+        let mut recency = Recency::default();
+        recency.touch(&"a".into());
+        recency.touch(&"b".into());
+        recency.touch(&"c".into());
+
+        // "a" is the tail (least-recently-used).
+        recency.remove(&"a".into());
+        assert_eq!(recency.lru_to_mru(), vec!["b".into(), "c".into()]);
+
+        // "c" is the head (most-recently-used).
+        recency.remove(&"c".into());
+        assert_eq!(recency.lru_to_mru(), vec!["b".into()]);
+
+        // The only remaining entry is both head and tail.
+        recency.remove(&"b".into());
+        assert!(recency.lru_to_mru().is_empty());
+    }
+
+    #[test]
+    fn remove_of_untracked_name_is_a_no_op() {
+        let mut recency = Recency::default();
+        recency.touch(&"a".into());
+
+        recency.remove(&"not-tracked".into());
+
+        assert_eq!(recency.lru_to_mru(), vec!["a".into()]);
+    }
+
     // TODO (future-highway): These tests need to be updated.
     // use cgroups_rs::{cgroup_builder::CgroupBuilder, hierarchies};
     //
@@ -30,12 +30,31 @@
 
 use crate::CellName;
 use aurae_executables::ExecutableName;
-use std::io;
+use std::{io, path::PathBuf};
 use thiserror::Error;
 use tracing::error;
 
 pub type Result<T> = std::result::Result<T, CellsError>;
 
+/// A resource tracked by the cell accounting subsystem and capped by an
+/// optional host-wide budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    Memory,
+    CpuQuota,
+    Pids,
+}
+
+impl std::fmt::Display for Resource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Resource::Memory => write!(f, "memory"),
+            Resource::CpuQuota => write!(f, "cpu quota"),
+            Resource::Pids => write!(f, "pids"),
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum CellsError {
     #[error("cell '{cell_name}' already exists'")]
@@ -82,4 +101,17 @@ pub enum CellsError {
     },
     #[error("failed to lock cells table")]
     FailedToObtainLock(),
+    #[error("cell '{cell_name}' could not be allocated: capacity of {capacity} cells reached and no idle cell was available to reclaim")]
+    CapacityExceeded { cell_name: CellName, capacity: usize },
+    #[error("failed to read cgroup root '{}' while reconciling cells: {source}", root.display())]
+    FailedToReadCgroupRoot { root: PathBuf, source: io::Error },
+    #[error("cell '{cell_name}' exists on disk but could not be reconciled: {source}")]
+    FailedToReconcileCell { cell_name: CellName, source: io::Error },
+    #[error("cell '{cell_name}' could not be allocated: {resource} budget of {available} remaining but {requested} requested")]
+    BudgetExceeded {
+        cell_name: CellName,
+        resource: Resource,
+        requested: i64,
+        available: i64,
+    },
 }